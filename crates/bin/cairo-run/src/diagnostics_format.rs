@@ -0,0 +1,175 @@
+//! Machine-readable renderings of compiler diagnostics.
+//!
+//! `DiagnosticsReporter` normally renders diagnostics as free text to a writer. For editors and
+//! CI, that requires regex-scraping stderr. This module turns the same structured diagnostic data
+//! into a stable JSON array or a SARIF document instead, so consumers can parse it precisely.
+
+use cairo_lang_compiler::diagnostics::{CompilerDiagnostic, CompilerDiagnosticLocation, ResolvedFix};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Output format for the diagnostics emitted by the `cairo-run` binary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum DiagnosticsFormat {
+    /// The default free-text rendering, identical to `DiagnosticsReporter::stderr`.
+    Human,
+    /// A stable JSON array, one object per diagnostic.
+    Json,
+    /// A SARIF 2.1.0 log, consumable by editors and CI problem matchers.
+    Sarif,
+}
+
+/// A single file+span location, in 1-based line/column coordinates.
+#[derive(Serialize)]
+struct JsonLocation {
+    file: String,
+    start_line: usize,
+    start_col: usize,
+    end_line: usize,
+    end_col: usize,
+}
+
+impl From<&CompilerDiagnosticLocation> for JsonLocation {
+    fn from(location: &CompilerDiagnosticLocation) -> Self {
+        Self {
+            file: location.file.clone(),
+            start_line: location.start_line,
+            start_col: location.start_col,
+            end_line: location.end_line,
+            end_col: location.end_col,
+        }
+    }
+}
+
+/// A mechanical fix suggestion, mirrored from `ResolvedFix`.
+#[derive(Serialize)]
+struct JsonFix {
+    description: String,
+    edits: Vec<JsonEdit>,
+}
+
+#[derive(Serialize)]
+struct JsonEdit {
+    #[serde(flatten)]
+    location: JsonLocation,
+    replacement: String,
+}
+
+#[derive(Serialize)]
+struct JsonSecondarySpan {
+    #[serde(flatten)]
+    location: JsonLocation,
+    label: String,
+}
+
+#[derive(Serialize)]
+struct JsonDiagnostic {
+    message: String,
+    severity: String,
+    code: Option<String>,
+    location: JsonLocation,
+    notes: Vec<String>,
+    secondary_spans: Vec<JsonSecondarySpan>,
+    fixes: Vec<JsonFix>,
+}
+
+fn to_json_fixes(fixes: &[ResolvedFix]) -> Vec<JsonFix> {
+    fixes
+        .iter()
+        .map(|fix| JsonFix {
+            description: fix.description.clone(),
+            edits: fix
+                .edits
+                .iter()
+                .map(|edit| JsonEdit {
+                    location: (&edit.location).into(),
+                    replacement: edit.replacement.clone(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn severity_str(severity: cairo_lang_diagnostics::Severity) -> &'static str {
+    match severity {
+        cairo_lang_diagnostics::Severity::Error => "error",
+        cairo_lang_diagnostics::Severity::Warning => "warning",
+    }
+}
+
+/// Renders the given diagnostics as a stable JSON array.
+pub fn to_json(diagnostics: &[CompilerDiagnostic]) -> String {
+    let entries: Vec<JsonDiagnostic> = diagnostics
+        .iter()
+        .map(|diagnostic| JsonDiagnostic {
+            message: diagnostic.message.clone(),
+            severity: severity_str(diagnostic.severity).to_string(),
+            code: diagnostic.code.clone(),
+            location: (&diagnostic.location).into(),
+            notes: diagnostic.notes.clone(),
+            secondary_spans: diagnostic
+                .secondary_spans
+                .iter()
+                .map(|(location, label)| JsonSecondarySpan {
+                    location: location.into(),
+                    label: label.clone(),
+                })
+                .collect(),
+            fixes: to_json_fixes(&diagnostic.fixes),
+        })
+        .collect();
+    serde_json::to_string_pretty(&entries).expect("diagnostics are always serializable")
+}
+
+/// Renders the given diagnostics as a SARIF 2.1.0 log.
+pub fn to_sarif(diagnostics: &[CompilerDiagnostic]) -> String {
+    let results: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut related_locations = vec![];
+            for (location, label) in &diagnostic.secondary_spans {
+                related_locations.push(json!({
+                    "message": { "text": label },
+                    "physicalLocation": physical_location(location),
+                }));
+            }
+            json!({
+                "ruleId": diagnostic.code,
+                "level": match diagnostic.severity {
+                    cairo_lang_diagnostics::Severity::Error => "error",
+                    cairo_lang_diagnostics::Severity::Warning => "warning",
+                },
+                "message": { "text": diagnostic.message },
+                "locations": [{ "physicalLocation": physical_location(&diagnostic.location) }],
+                "relatedLocations": related_locations,
+            })
+        })
+        .collect();
+    let sarif = json!({
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "cairo-run",
+                    "informationUri": "https://github.com/starkware-libs/cairo",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_string_pretty(&sarif).expect("sarif document is always serializable")
+}
+
+fn physical_location(location: &CompilerDiagnosticLocation) -> Value {
+    json!({
+        "artifactLocation": { "uri": location.file },
+        "region": {
+            "startLine": location.start_line,
+            "startColumn": location.start_col,
+            "endLine": location.end_line,
+            "endColumn": location.end_col,
+        },
+    })
+}
@@ -0,0 +1,52 @@
+//! Per-lint severity overrides for lowering diagnostics, following the rustc/clippy model of
+//! addressable lint codes rather than the all-or-nothing `--allow-warnings` switch.
+
+use std::collections::HashMap;
+
+use cairo_lang_diagnostics::Severity;
+use clap::ValueEnum;
+
+/// The severity a lint should be reported with. Unlike [`Severity`], this also allows silencing a
+/// lint entirely via [`LintLevel::Allow`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LintLevel {
+    /// Don't report this lint at all.
+    Allow,
+    /// Report this lint as a warning. Like any warning, it fails compilation unless
+    /// `--allow-warnings` is also passed.
+    Warn,
+    /// Report this lint as a hard error.
+    Deny,
+}
+
+impl LintLevel {
+    /// Converts this level to the severity consumers should see, or `None` if the lint is
+    /// allowed and should be dropped entirely.
+    pub fn to_severity(self) -> Option<Severity> {
+        match self {
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(Severity::Warning),
+            LintLevel::Deny => Some(Severity::Error),
+        }
+    }
+}
+
+/// Builds a map from lint code to its overridden severity (`None` meaning "silence entirely"),
+/// ready to hand to `DiagnosticsReporter::with_lint_levels`, from repeated
+/// `--allow`/`--deny`/`--warn <code>` CLI occurrences. If the same code is passed to more than one
+/// of the three flags, `deny` wins over `warn`, which wins over `allow`.
+pub fn build_overrides(
+    allow: &[String],
+    deny: &[String],
+    warn: &[String],
+) -> HashMap<String, Option<Severity>> {
+    let mut overrides = HashMap::new();
+    for (codes, level) in
+        [(allow, LintLevel::Allow), (warn, LintLevel::Warn), (deny, LintLevel::Deny)]
+    {
+        for code in codes {
+            overrides.insert(code.clone(), level.to_severity());
+        }
+    }
+    overrides
+}
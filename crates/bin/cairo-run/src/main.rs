@@ -16,6 +16,12 @@ use cairo_lang_sierra_generator::replace_ids::{DebugReplacer, SierraIdReplacer};
 use cairo_lang_starknet::contract::get_contracts_info;
 use cairo_lang_utils::arc_unwrap_or_clone;
 use clap::Parser;
+use diagnostics_format::DiagnosticsFormat;
+use profiler_format::ProfilerFormat;
+
+mod diagnostics_format;
+mod lint_levels;
+mod profiler_format;
 
 /// Command line args parser.
 /// Exits with 0/1 if the input is formatted correctly/incorrectly.
@@ -30,6 +36,15 @@ struct Args {
     /// Allows the compilation to succeed with warnings.
     #[arg(long)]
     allow_warnings: bool,
+    /// Silences a named lowering lint (e.g. `unreachable-code`). May be passed multiple times.
+    #[arg(long = "allow", value_name = "LINT")]
+    allow_lints: Vec<String>,
+    /// Reports a named lowering lint as a hard error. May be passed multiple times.
+    #[arg(long = "deny", value_name = "LINT")]
+    deny_lints: Vec<String>,
+    /// Reports a named lowering lint as a warning. May be passed multiple times.
+    #[arg(long = "warn", value_name = "LINT")]
+    warn_lints: Vec<String>,
     /// In cases where gas is available, the amount of provided gas.
     #[arg(long)]
     available_gas: Option<usize>,
@@ -39,6 +54,16 @@ struct Args {
     /// Whether to run the profiler.
     #[arg(long, default_value_t = false)]
     run_profiler: bool,
+    /// The format in which to emit the profiler output.
+    #[arg(long, value_enum, default_value_t = ProfilerFormat::Text)]
+    profiler_format: ProfilerFormat,
+    /// Where to write the profiler output. Defaults to stdout for `text`, and to
+    /// `profile.folded`/`profile.pb` for `folded`/`pprof` respectively.
+    #[arg(long)]
+    profiler_output: Option<PathBuf>,
+    /// The format in which to emit diagnostics.
+    #[arg(long, value_enum, default_value_t = DiagnosticsFormat::Human)]
+    diagnostics_format: DiagnosticsFormat,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -55,8 +80,31 @@ fn main() -> anyhow::Result<()> {
     if args.allow_warnings {
         reporter = reporter.allow_warnings();
     }
-    if reporter.check(db) {
-        anyhow::bail!("failed to compile: {}", args.path.display());
+    let lint_overrides =
+        lint_levels::build_overrides(&args.allow_lints, &args.deny_lints, &args.warn_lints);
+    if !lint_overrides.is_empty() {
+        reporter = reporter.with_lint_levels(lint_overrides);
+    }
+    match args.diagnostics_format {
+        DiagnosticsFormat::Human => {
+            if reporter.check(db) {
+                anyhow::bail!("failed to compile: {}", args.path.display());
+            }
+        }
+        DiagnosticsFormat::Json | DiagnosticsFormat::Sarif => {
+            let collected = reporter.collect(db);
+            let has_errors =
+                collected.iter().any(|diagnostic| diagnostic.fails_build(args.allow_warnings));
+            let rendered = match args.diagnostics_format {
+                DiagnosticsFormat::Json => diagnostics_format::to_json(&collected),
+                DiagnosticsFormat::Sarif => diagnostics_format::to_sarif(&collected),
+                DiagnosticsFormat::Human => unreachable!(),
+            };
+            println!("{rendered}");
+            if has_errors {
+                anyhow::bail!("failed to compile: {}", args.path.display());
+            }
+        }
     }
 
     let SierraProgramWithDebug { program: sierra_program, debug_info } = arc_unwrap_or_clone(
@@ -89,16 +137,34 @@ fn main() -> anyhow::Result<()> {
         .with_context(|| "Failed to run the function.")?;
 
     if args.run_profiler {
-        let profiling_info_processor = ProfilingInfoProcessor::new(
-            Some(db),
-            sierra_program,
-            debug_info.statements_locations.get_statements_functions_map(db),
-        );
+        let statements_functions_map = debug_info.statements_locations.get_statements_functions_map(db);
         match result.profiling_info {
-            Some(raw_profiling_info) => {
-                let profiling_info = profiling_info_processor.process(&raw_profiling_info);
-                println!("Profiling info:\n{}", profiling_info);
-            }
+            Some(raw_profiling_info) => match args.profiler_format {
+                ProfilerFormat::Text => {
+                    let profiling_info_processor = ProfilingInfoProcessor::new(
+                        Some(db),
+                        sierra_program,
+                        statements_functions_map,
+                    );
+                    let profiling_info = profiling_info_processor.process(&raw_profiling_info);
+                    match args.profiler_output.as_deref() {
+                        Some(path) => write_profiler_output(
+                            Some(path),
+                            "",
+                            format!("{profiling_info}\n").as_bytes(),
+                        )?,
+                        None => println!("Profiling info:\n{}", profiling_info),
+                    }
+                }
+                ProfilerFormat::Folded => {
+                    let folded = profiler_format::to_folded(&raw_profiling_info, &statements_functions_map);
+                    write_profiler_output(args.profiler_output.as_deref(), "profile.folded", folded.as_bytes())?;
+                }
+                ProfilerFormat::Pprof => {
+                    let pprof = profiler_format::to_pprof(&raw_profiling_info, &statements_functions_map);
+                    write_profiler_output(args.profiler_output.as_deref(), "profile.pb", &pprof)?;
+                }
+            },
             None => println!("Warning: Profiling info not found."),
         }
     }
@@ -133,3 +199,12 @@ fn main() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+/// Writes profiler output bytes to `path`, falling back to `default_path` when the user did not
+/// provide one via `--profiler-output`.
+fn write_profiler_output(path: Option<&Path>, default_path: &str, bytes: &[u8]) -> anyhow::Result<()> {
+    let path = path.unwrap_or_else(|| Path::new(default_path));
+    std::fs::write(path, bytes).with_context(|| format!("Failed to write profiler output to {}", path.display()))?;
+    println!("Profiling info written to: {}", path.display());
+    Ok(())
+}
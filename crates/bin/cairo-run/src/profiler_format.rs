@@ -0,0 +1,222 @@
+//! Renderings of raw profiling samples for external performance tooling.
+//!
+//! `ProfilingInfoProcessor` already has everything needed to build a flamegraph: a map from each
+//! Sierra statement to the stack of function names it executes within, and a per-statement sample
+//! count. This turns that data into collapsed "folded stack" lines (consumable directly by
+//! `inferno`/`flamegraph.pl`) or into a pprof protobuf profile, instead of only the `Display`
+//! summary printed by the `text` format.
+
+use std::collections::HashMap;
+
+use cairo_lang_runner::profiling::ProfilingInfo;
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+/// Output format for `--run-profiler`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProfilerFormat {
+    /// The existing human readable `Display` summary.
+    Text,
+    /// Collapsed "stack;stack;...;stack count" lines, one per leaf stack.
+    Folded,
+    /// A pprof (<https://github.com/google/pprof>) protobuf profile.
+    Pprof,
+}
+
+/// Aggregates the per-statement sample weights into per-stack sample counts, using the given
+/// statement-to-function-stack map.
+fn stack_weights(
+    profiling_info: &ProfilingInfo,
+    statements_functions_map: &OrderedHashMap<StatementIdx, Vec<String>>,
+) -> Vec<(Vec<String>, usize)> {
+    let mut weights: HashMap<Vec<String>, usize> = HashMap::new();
+    for (statement_idx, weight) in profiling_info.sierra_statement_weights.iter() {
+        let stack = statements_functions_map.get(statement_idx).cloned().unwrap_or_default();
+        *weights.entry(stack).or_insert(0) += weight;
+    }
+    let mut weights: Vec<_> = weights.into_iter().collect();
+    weights.sort_by(|(stack_a, _), (stack_b, _)| stack_a.cmp(stack_b));
+    weights
+}
+
+/// Renders `profiling_info` as collapsed folded stacks, one line per stack:
+/// `func_a;func_b;func_c <count>`.
+pub fn to_folded(
+    profiling_info: &ProfilingInfo,
+    statements_functions_map: &OrderedHashMap<StatementIdx, Vec<String>>,
+) -> String {
+    stack_weights(profiling_info, statements_functions_map)
+        .into_iter()
+        .filter(|(_, weight)| *weight > 0)
+        .map(|(stack, weight)| format!("{} {weight}", stack.join(";")))
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+/// Renders `profiling_info` as a minimal pprof protobuf profile: a single "samples"/"count" value
+/// type, one sample per stack with its aggregated weight as the sample count, and a real
+/// `Location`/`Function` per stack frame so `Sample.location_id` resolves to something - standard
+/// tooling (`go tool pprof`, `inferno`) rejects a profile with no sample types and cannot symbolize
+/// a location id with no backing `Location` message.
+pub fn to_pprof(
+    profiling_info: &ProfilingInfo,
+    statements_functions_map: &OrderedHashMap<StatementIdx, Vec<String>>,
+) -> Vec<u8> {
+    let weights = stack_weights(profiling_info, statements_functions_map);
+
+    // Function name -> pprof string table index. Index 0 is reserved for the empty string.
+    let mut string_table = vec![String::new()];
+    let samples_type_idx = string_table.len() as i64;
+    string_table.push("samples".to_string());
+    let count_unit_idx = string_table.len() as i64;
+    string_table.push("count".to_string());
+
+    // Function name -> id of the single Location standing in for that frame. One Location per
+    // named frame is enough for a flat (line-less) profile; `Sample.location_id` must point here,
+    // not at a bare Function id, since Location is pprof's addressable stack-frame unit.
+    let mut location_ids: HashMap<String, u64> = HashMap::new();
+    let mut functions = Vec::new();
+    let mut locations = Vec::new();
+    let mut get_location_id = |name: &str| -> u64 {
+        if let Some(id) = location_ids.get(name) {
+            return *id;
+        }
+        let name_idx = string_table.len() as i64;
+        string_table.push(name.to_string());
+        let function_id = functions.len() as u64 + 1;
+        functions.push(pprof_proto::Function { id: function_id, name: name_idx, system_name: name_idx });
+        let location_id = locations.len() as u64 + 1;
+        locations.push(pprof_proto::Location {
+            id: location_id,
+            lines: vec![pprof_proto::Line { function_id, line: 0 }],
+        });
+        location_ids.insert(name.to_string(), location_id);
+        location_id
+    };
+
+    let samples = weights
+        .into_iter()
+        .map(|(stack, weight)| {
+            let location_ids =
+                stack.iter().rev().map(|name| get_location_id(name)).collect::<Vec<_>>();
+            pprof_proto::Sample { location_ids, value: weight as i64 }
+        })
+        .collect();
+
+    let profile = pprof_proto::Profile {
+        sample_types: vec![pprof_proto::ValueType { r#type: samples_type_idx, unit: count_unit_idx }],
+        string_table,
+        functions,
+        locations,
+        samples,
+    };
+    profile.encode()
+}
+
+/// A bare-bones pprof protobuf encoder covering only the fields this module needs to emit - just
+/// enough to produce a valid profile, not a general-purpose pprof client.
+mod pprof_proto {
+    /// `Profile.sample_type` entry: indices into the string table naming what a sample value
+    /// measures (`type`) and its unit.
+    pub struct ValueType {
+        pub r#type: i64,
+        pub unit: i64,
+    }
+
+    pub struct Function {
+        pub id: u64,
+        pub name: i64,
+        pub system_name: i64,
+    }
+
+    /// A single stack frame, referencing the `Function` it's a call into. `Sample.location_id`
+    /// points at these, never directly at a `Function` id.
+    pub struct Location {
+        pub id: u64,
+        pub lines: Vec<Line>,
+    }
+
+    pub struct Line {
+        pub function_id: u64,
+        pub line: i64,
+    }
+
+    pub struct Sample {
+        pub location_ids: Vec<u64>,
+        pub value: i64,
+    }
+
+    pub struct Profile {
+        pub sample_types: Vec<ValueType>,
+        pub string_table: Vec<String>,
+        pub functions: Vec<Function>,
+        pub locations: Vec<Location>,
+        pub samples: Vec<Sample>,
+    }
+
+    impl Profile {
+        pub fn encode(&self) -> Vec<u8> {
+            let mut out = Vec::new();
+            for sample_type in &self.sample_types {
+                let mut buf = Vec::new();
+                write_varint_field(&mut buf, 1, sample_type.r#type as u64);
+                write_varint_field(&mut buf, 2, sample_type.unit as u64);
+                write_tagged(&mut out, 1, &buf);
+            }
+            for sample in &self.samples {
+                let mut buf = Vec::new();
+                for location_id in &sample.location_ids {
+                    write_varint_field(&mut buf, 1, *location_id);
+                }
+                write_varint_field(&mut buf, 2, sample.value as u64);
+                write_tagged(&mut out, 2, &buf);
+            }
+            for location in &self.locations {
+                let mut buf = Vec::new();
+                write_varint_field(&mut buf, 1, location.id);
+                for line in &location.lines {
+                    let mut line_buf = Vec::new();
+                    write_varint_field(&mut line_buf, 1, line.function_id);
+                    write_varint_field(&mut line_buf, 2, line.line as u64);
+                    write_tagged(&mut buf, 4, &line_buf);
+                }
+                write_tagged(&mut out, 4, &buf);
+            }
+            for function in &self.functions {
+                let mut buf = Vec::new();
+                write_varint_field(&mut buf, 1, function.id);
+                write_varint_field(&mut buf, 2, function.name as u64);
+                write_varint_field(&mut buf, 3, function.system_name as u64);
+                write_tagged(&mut out, 5, &buf);
+            }
+            for entry in &self.string_table {
+                write_tagged(&mut out, 6, entry.as_bytes());
+            }
+            out
+        }
+    }
+
+    fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                break;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    fn write_tagged(out: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+        write_varint(out, (field << 3) | 2);
+        write_varint(out, bytes.len() as u64);
+        out.extend_from_slice(bytes);
+    }
+
+    fn write_varint_field(out: &mut Vec<u8>, field: u64, value: u64) {
+        write_varint(out, (field << 3) | 0);
+        write_varint(out, value);
+    }
+}
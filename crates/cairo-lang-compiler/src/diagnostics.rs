@@ -0,0 +1,256 @@
+//! Collects and renders the diagnostics produced while compiling a project: parser, semantic and
+//! lowering diagnostics. Only the lowering diagnostics carry the richer structure (secondary
+//! spans, stable lint codes and mechanical fixes); the others are rendered with message, severity,
+//! location and notes only.
+
+use std::collections::HashMap;
+use std::io::Write as _;
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_diagnostics::{DiagnosticEntry, DiagnosticLocation, Severity};
+use cairo_lang_filesystem::span::TextOffset;
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_lowering::diagnostic::{DiagnosticFix, LoweringDiagnostic};
+use cairo_lang_semantic::db::SemanticGroup;
+
+/// A single diagnostic, flattened to the data every renderer (text, JSON, SARIF) needs: a
+/// message, a severity, a primary location, optional notes, secondary labeled spans and mechanical
+/// fix suggestions.
+#[derive(Clone, Debug)]
+pub struct CompilerDiagnostic {
+    pub message: String,
+    pub severity: Severity,
+    pub code: Option<String>,
+    pub location: CompilerDiagnosticLocation,
+    pub notes: Vec<String>,
+    pub secondary_spans: Vec<(CompilerDiagnosticLocation, String)>,
+    pub fixes: Vec<ResolvedFix>,
+}
+
+impl CompilerDiagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Whether this diagnostic should fail compilation: every error does, and - matching the
+    /// original reporter's behavior - so does a warning, unless the caller opted into
+    /// `allow_warnings`.
+    pub fn fails_build(&self, allow_warnings: bool) -> bool {
+        match self.severity {
+            Severity::Error => true,
+            Severity::Warning => !allow_warnings,
+        }
+    }
+}
+
+/// A file + 1-based line/column span, the common denominator every diagnostic sink renders.
+#[derive(Clone, Debug)]
+pub struct CompilerDiagnosticLocation {
+    pub file: String,
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+/// A [`DiagnosticFix`] with its edits resolved to renderer-friendly file+line/col locations.
+#[derive(Clone, Debug)]
+pub struct ResolvedFix {
+    pub description: String,
+    pub edits: Vec<ResolvedEdit>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ResolvedEdit {
+    pub location: CompilerDiagnosticLocation,
+    pub replacement: String,
+}
+
+/// Builds and renders the diagnostics for a compilation, and decides whether they contain a hard
+/// error.
+pub struct DiagnosticsReporter<'a> {
+    allow_warnings: bool,
+    /// Per-lint-code severity overrides, keyed by `LoweringDiagnosticKind::code()`. A `None`
+    /// value means the lint is silenced entirely.
+    lint_levels: HashMap<String, Option<Severity>>,
+    writer: Box<dyn std::io::Write + 'a>,
+}
+
+impl<'a> DiagnosticsReporter<'a> {
+    /// A reporter that renders diagnostics as free text to stderr, matching the CLI's original
+    /// behavior.
+    pub fn stderr() -> Self {
+        Self {
+            allow_warnings: false,
+            lint_levels: HashMap::new(),
+            writer: Box::new(std::io::stderr()),
+        }
+    }
+
+    /// Allows the compilation to succeed even if warnings were reported.
+    pub fn allow_warnings(mut self) -> Self {
+        self.allow_warnings = true;
+        self
+    }
+
+    /// Overrides the severity of named lowering lints (see `LoweringDiagnosticKind::code`),
+    /// independently of the blanket `allow_warnings` switch.
+    pub fn with_lint_levels(mut self, lint_levels: HashMap<String, Option<Severity>>) -> Self {
+        self.lint_levels = lint_levels;
+        self
+    }
+
+    /// Renders every diagnostic as text to this reporter's writer, returning `true` if
+    /// compilation should be considered to have failed.
+    pub fn check(&mut self, db: &dyn LoweringGroup) -> bool {
+        let diagnostics = self.collect(db);
+        let mut has_errors = false;
+        for diagnostic in &diagnostics {
+            has_errors |= diagnostic.fails_build(self.allow_warnings);
+            let location = &diagnostic.location;
+            let _ = writeln!(
+                self.writer,
+                "{}: {}:{}:{}: {}",
+                severity_label(diagnostic.severity),
+                location.file,
+                location.start_line,
+                location.start_col,
+                diagnostic.message
+            );
+            for note in &diagnostic.notes {
+                let _ = writeln!(self.writer, "  note: {note}");
+            }
+        }
+        has_errors
+    }
+
+    /// Collects every diagnostic reported while compiling `db`'s crates - parser (syntax) and
+    /// semantic diagnostics as well as lowering diagnostics, matching what the original
+    /// `DiagnosticsReporter::check` reported - with lint-level overrides already applied to the
+    /// lowering diagnostics (an allowed lint is dropped; a warned/denied lint has its severity
+    /// replaced).
+    pub fn collect(&self, db: &dyn LoweringGroup) -> Vec<CompilerDiagnostic> {
+        let mut collected = Vec::new();
+        let semantic_db: &dyn SemanticGroup = db.upcast();
+        let defs_db: &dyn DefsGroup = db.upcast();
+        for crate_id in db.crates() {
+            for module_id in db.crate_modules(crate_id).iter() {
+                for diagnostic in defs_db.module_file_diagnostics(*module_id).get_all() {
+                    collected.push(render_generic_diagnostic(defs_db, semantic_db, &diagnostic));
+                }
+                for diagnostic in semantic_db.module_semantic_diagnostics(*module_id).get_all() {
+                    collected.push(render_generic_diagnostic(semantic_db, semantic_db, &diagnostic));
+                }
+                for diagnostic in db.module_lowering_diagnostics(*module_id).get_all() {
+                    if let Some(rendered) = self.render_lowering_diagnostic(db, &diagnostic) {
+                        collected.push(rendered);
+                    }
+                }
+            }
+        }
+        collected
+    }
+
+    fn render_lowering_diagnostic(
+        &self,
+        db: &dyn LoweringGroup,
+        diagnostic: &LoweringDiagnostic,
+    ) -> Option<CompilerDiagnostic> {
+        let semantic_db = db.upcast();
+        let code = diagnostic.kind.code().to_string();
+        let severity = match self.lint_levels.get(&code) {
+            Some(None) => return None,
+            Some(Some(overridden)) => *overridden,
+            None => diagnostic.kind.default_severity(),
+        };
+        let location = diagnostic.location(semantic_db);
+        Some(CompilerDiagnostic {
+            message: diagnostic.format(semantic_db),
+            severity,
+            code: Some(code),
+            location: resolve_location(semantic_db, &location),
+            notes: diagnostic.notes(semantic_db).iter().map(|note| note.text.clone()).collect(),
+            secondary_spans: diagnostic
+                .secondary_spans(semantic_db)
+                .into_iter()
+                .map(|(location, label)| (resolve_location(semantic_db, &location), label))
+                .collect(),
+            fixes: diagnostic
+                .fixes(semantic_db)
+                .into_iter()
+                .map(|fix| resolve_fix(semantic_db, fix))
+                .collect(),
+        })
+    }
+}
+
+/// Renders a parser or semantic diagnostic - anything that only implements the plain
+/// `DiagnosticEntry` trait, without the lowering-specific lint codes/secondary
+/// spans/fixes - to the same flattened `CompilerDiagnostic` shape as a lowering diagnostic.
+///
+/// `entry_db` is whatever database type `T` itself needs to format/locate/annotate itself;
+/// `semantic_db` is used only to resolve the resulting span to file+line/col, which only needs
+/// `FilesGroup` (a `SemanticGroup` supertrait) and is independent of `T`'s own database type.
+fn render_generic_diagnostic<T: DiagnosticEntry>(
+    entry_db: &T::DbType,
+    semantic_db: &dyn SemanticGroup,
+    diagnostic: &T,
+) -> CompilerDiagnostic {
+    let location = diagnostic.location(entry_db);
+    CompilerDiagnostic {
+        message: diagnostic.format(entry_db),
+        severity: diagnostic.severity(),
+        code: None,
+        location: resolve_location(semantic_db, &location),
+        notes: diagnostic.notes(entry_db).iter().map(|note| note.text.clone()).collect(),
+        secondary_spans: vec![],
+        fixes: vec![],
+    }
+}
+
+fn resolve_location(db: &dyn SemanticGroup, location: &DiagnosticLocation) -> CompilerDiagnosticLocation {
+    let start = resolve_offset(db, location.file_id, location.span.start);
+    let end = resolve_offset(db, location.file_id, location.span.end);
+    CompilerDiagnosticLocation {
+        file: location.file_id.full_path(db.upcast()),
+        start_line: start.0,
+        start_col: start.1,
+        end_line: end.0,
+        end_col: end.1,
+    }
+}
+
+fn resolve_offset(
+    db: &dyn SemanticGroup,
+    file_id: cairo_lang_filesystem::ids::FileId,
+    offset: TextOffset,
+) -> (usize, usize) {
+    offset
+        .position_in_file(db.upcast(), file_id)
+        .map(|position| (position.line + 1, position.col + 1))
+        .unwrap_or((0, 0))
+}
+
+fn resolve_fix(db: &dyn SemanticGroup, fix: DiagnosticFix) -> ResolvedFix {
+    ResolvedFix {
+        description: fix.description,
+        edits: fix
+            .edits
+            .into_iter()
+            .map(|(file_id, span, replacement)| ResolvedEdit {
+                location: resolve_location(
+                    db,
+                    &DiagnosticLocation { file_id, span },
+                ),
+                replacement,
+            })
+            .collect(),
+    }
+}
+
+fn severity_label(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
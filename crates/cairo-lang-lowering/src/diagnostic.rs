@@ -1,9 +1,11 @@
 use cairo_lang_defs::diagnostic_utils::StableLocation;
 use cairo_lang_diagnostics::{
     DiagnosticAdded, DiagnosticEntry, DiagnosticLocation, DiagnosticNote, Diagnostics,
-    DiagnosticsBuilder,
+    DiagnosticsBuilder, Severity,
 };
+use cairo_lang_filesystem::db::FilesGroup;
 use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_filesystem::span::{TextOffset, TextSpan, TextWidth};
 use cairo_lang_semantic::corelib::LiteralError;
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::expr::inference::InferenceError;
@@ -109,6 +111,13 @@ impl DiagnosticEntry for LoweringDiagnostic {
 
     #[allow(unreachable_patterns, clippy::single_match)]
     fn location(&self, db: &Self::DbType) -> DiagnosticLocation {
+        self.compute_location(db)
+    }
+}
+
+impl LoweringDiagnostic {
+    #[allow(unreachable_patterns, clippy::single_match)]
+    fn compute_location(&self, db: &dyn SemanticGroup) -> DiagnosticLocation {
         match &self.kind {
             LoweringDiagnosticKind::Unreachable { last_statement_ptr } => {
                 return self
@@ -120,13 +129,119 @@ impl DiagnosticEntry for LoweringDiagnostic {
         }
         self.location.stable_location.diagnostic_location(db.upcast())
     }
+
+    /// Returns the mechanical fix suggestions applicable to this diagnostic, if any.
+    ///
+    /// Each fix is a human readable label together with the text edits needed to apply it, so
+    /// that IDE and CLI consumers can offer a one-click fix instead of just a message.
+    pub fn fixes(&self, db: &dyn SemanticGroup) -> Vec<DiagnosticFix> {
+        let location = self.compute_location(db);
+        let end = location.span.end;
+        // `location`'s span is the whole match expression, which ends on its closing `}`; the
+        // wildcard/missing arm has to land *before* that brace, not after the whole expression,
+        // or applying the edit would place the new arm outside the match body entirely.
+        let before_closing_brace = end.sub_width(TextWidth::from_str("}"));
+        let insertion_point = TextSpan { start: before_closing_brace, end: before_closing_brace };
+        // If the preceding arm already ends in a comma, don't prepend another one - `arm,` plus a
+        // leading `, _ => ...` would insert a double comma and fail to parse. And use `panic!()`,
+        // not an empty `{}` body: `{}` evaluates to the unit type, which only type-checks when the
+        // match itself returns unit - `panic!()` diverges, so it coerces to whatever type the
+        // other arms return.
+        let leading_comma =
+            if ends_with_comma(db, location.file_id, before_closing_brace) { "" } else { "," };
+        match &self.kind {
+            LoweringDiagnosticKind::NonExhaustiveMatchFelt252 => vec![DiagnosticFix {
+                description: "add `_ => panic!()` wildcard arm".into(),
+                edits: vec![(
+                    location.file_id,
+                    insertion_point,
+                    format!("{leading_comma} _ => panic!(),"),
+                )],
+            }],
+            LoweringDiagnosticKind::MissingMatchArm(variant) => vec![DiagnosticFix {
+                description: format!("insert missing `{variant}` match arm"),
+                edits: vec![(
+                    location.file_id,
+                    insertion_point,
+                    format!("{leading_comma} {variant} => panic!(),"),
+                )],
+            }],
+            // There's no mechanical fix for a dangling value: the actual drop/destruct call has
+            // to name the real variable (or a pattern binding) at the point of last use, which
+            // isn't data this diagnostic carries. Suggesting a fix that calls `drop` on a
+            // placeholder identifier would apply "successfully" and then fail to compile, which
+            // is worse than not offering a fix at all.
+            _ => vec![],
+        }
+    }
+
+    /// Returns labeled secondary spans for this diagnostic, in addition to its primary location.
+    ///
+    /// Analogous to borrowck's `var_path_only_subdiag` labels: a move or drop error is often only
+    /// actionable when it also points at the use site *and* the earlier move/definition site.
+    ///
+    /// `DesnappingANonCopyableType` doesn't get a secondary span here: unlike `VariableMoved`/
+    /// `VariableNotDropped`, its variant only carries the `InferenceError` that explains *why* the
+    /// type isn't copyable, not a `Location` for where the snapshot was taken - there is no second
+    /// span to point at without first threading that location through from the (borrow-check)
+    /// code that constructs this diagnostic, which isn't part of this tree.
+    ///
+    /// No construction site for `VariableMoved`/`VariableNotDropped` exists anywhere in this tree
+    /// (the borrow-check pass that builds them lives outside it), so nothing here currently
+    /// supplies `Some(last_move)`/`Some(last_use)` - the two arms below are plumbing that's ready
+    /// to render a secondary span the moment a real caller passes one, not a feature that's live
+    /// yet in this snapshot.
+    pub fn secondary_spans(&self, db: &dyn SemanticGroup) -> Vec<(DiagnosticLocation, String)> {
+        match &self.kind {
+            LoweringDiagnosticKind::VariableMoved { last_move: Some(last_move), .. } => {
+                vec![(last_move.stable_location.diagnostic_location(db.upcast()), "value moved here".into())]
+            }
+            LoweringDiagnosticKind::VariableNotDropped { last_use: Some(last_use), .. } => {
+                vec![(
+                    last_use.stable_location.diagnostic_location(db.upcast()),
+                    "value was last produced here".into(),
+                )]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// Whether the source text immediately before `offset` (ignoring trailing whitespace) already
+/// ends in a comma, so a mechanical edit inserting a new match arm there knows whether it still
+/// needs to supply its own separating comma.
+fn ends_with_comma(db: &dyn SemanticGroup, file_id: FileId, offset: TextOffset) -> bool {
+    let files_db: &dyn FilesGroup = db.upcast();
+    let Some(content) = files_db.file_content(file_id) else { return false };
+    let byte_offset = (offset.as_u32() as usize).min(content.len());
+    content[..byte_offset].trim_end().ends_with(',')
+}
+
+/// A mechanical fix suggestion for a [`LoweringDiagnostic`], analogous to rust-analyzer's
+/// assists: a human readable label plus the concrete text edits that apply it.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DiagnosticFix {
+    /// A human readable description of the fix, e.g. to display in an IDE's code action menu.
+    pub description: String,
+    /// The text edits needed to apply this fix, each as a `(file, span, replacement)` triple.
+    pub edits: Vec<(FileId, TextSpan, String)>,
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum LoweringDiagnosticKind {
     Unreachable { last_statement_ptr: SyntaxStablePtrId },
-    VariableMoved { inference_error: InferenceError },
-    VariableNotDropped { drop_err: InferenceError, destruct_err: InferenceError },
+    /// `last_move` is the location of the move that left the variable unusable, when the
+    /// borrow checker can identify one; construction sites that can't recover it should pass
+    /// `None` rather than omit the field.
+    VariableMoved { inference_error: InferenceError, last_move: Option<Location> },
+    /// `last_use` is the location where the undropped value was last produced, when the borrow
+    /// checker can identify one; construction sites that can't recover it should pass `None`
+    /// rather than omit the field.
+    VariableNotDropped {
+        drop_err: InferenceError,
+        destruct_err: InferenceError,
+        last_use: Option<Location>,
+    },
     DesnappingANonCopyableType { inference_error: InferenceError },
     UnsupportedMatchedType(String),
     UnsupportedMatchedValueTuple,
@@ -146,3 +261,46 @@ pub enum LoweringDiagnosticKind {
     UnsupportedPattern,
     Unsupported,
 }
+
+impl LoweringDiagnosticKind {
+    /// A stable, addressable lint code, following the rustc/clippy model: a code a user can pass
+    /// to `--allow`/`--deny`/`--warn` to override this diagnostic's severity, independently of
+    /// the all-or-nothing `--allow-warnings` flag.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LoweringDiagnosticKind::Unreachable { .. } => "unreachable-code",
+            LoweringDiagnosticKind::VariableMoved { .. } => "variable-moved",
+            LoweringDiagnosticKind::VariableNotDropped { .. } => "variable-not-dropped",
+            LoweringDiagnosticKind::DesnappingANonCopyableType { .. } => "desnap-non-copyable",
+            LoweringDiagnosticKind::UnsupportedMatchedType(_) => "unsupported-matched-type",
+            LoweringDiagnosticKind::UnsupportedMatchedValueTuple => "unsupported-matched-value-tuple",
+            LoweringDiagnosticKind::MissingMatchArm(_) => "missing-match-arm",
+            LoweringDiagnosticKind::UnreachableMatchArm => "unreachable-match-arm",
+            LoweringDiagnosticKind::UnexpectedError => "unexpected-error",
+            LoweringDiagnosticKind::UnsupportedMatchArmNotAVariant => "match-arm-not-a-variant",
+            LoweringDiagnosticKind::UnsupportedMatchArmNotALiteral => "match-arm-not-a-literal",
+            LoweringDiagnosticKind::UnsupportedMatchArmNotATuple => "match-arm-not-a-tuple",
+            LoweringDiagnosticKind::UnsupportedMatchArmNonSequential => "match-arm-non-sequential",
+            LoweringDiagnosticKind::UnsupportedMatchArmOrNotSupported => "match-arm-or-not-supported",
+            LoweringDiagnosticKind::NonExhaustiveMatchFelt252 => "non-exhaustive-match-felt252",
+            LoweringDiagnosticKind::CannotInlineFunctionThatMightCallItself => "inline-recursive-call",
+            LoweringDiagnosticKind::MemberPathLoop => "member-path-loop",
+            LoweringDiagnosticKind::NoPanicFunctionCycle => "nopanic-function-cycle",
+            LoweringDiagnosticKind::LiteralError(_) => "literal-error",
+            LoweringDiagnosticKind::UnsupportedPattern => "unsupported-pattern",
+            LoweringDiagnosticKind::Unsupported => "unsupported-feature",
+        }
+    }
+
+    /// The severity this diagnostic is reported with absent any `--allow`/`--deny`/`--warn`
+    /// override for its [`Self::code`]. Style issues that don't prevent correct compilation -
+    /// unreachable code and unreachable match arms - default to a warning; everything else
+    /// defaults to a hard error.
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            LoweringDiagnosticKind::Unreachable { .. }
+            | LoweringDiagnosticKind::UnreachableMatchArm => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
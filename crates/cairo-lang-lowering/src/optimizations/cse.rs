@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+
+use cairo_lang_semantic::items::functions::GenericFunctionId;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+
+use crate::db::LoweringGroup;
+use crate::ids::FunctionId;
+use crate::objects::{
+    FlatBlockEnd, MatchInfo, Statement, StatementCall, StatementEnumConstruct,
+    StatementStructConstruct, StatementStructDestructure, VarUsage,
+};
+use crate::{BlockId, FlatLowered, VariableId};
+
+/// Eliminates common subexpressions from a lowered function.
+///
+/// Loop unrolling duplicates identical pure calls with the same argument variables, bloating the
+/// generated Sierra and the compiler's peak memory. This walks each block's statements in
+/// dominance order, keying every statement proven side-effect-free and deterministic by
+/// `(function, ordered input vars)`. The first occurrence of a key is kept; a later duplicate is
+/// deleted and its output variables are rewritten to reuse the first occurrence's outputs, but
+/// only when that first occurrence actually dominates the duplicate - two sibling branches (e.g.
+/// two match arms) can both contain an "earlier" identical call without either dominating the
+/// other, and collapsing across them would leave the losing arm referencing an undefined value.
+pub fn cse(db: &dyn LoweringGroup, lowered: &mut FlatLowered) {
+    if lowered.blocks.is_empty() {
+        return;
+    }
+    let dominators = Dominators::compute(lowered);
+
+    // Maps a variable to the variable it was unified with, if it was proven redundant.
+    let mut rebinding: OrderedHashMap<VariableId, VariableId> = OrderedHashMap::default();
+    // Maps a CSE key to the block and output variables of its first occurrence.
+    let mut seen: HashMap<(FunctionId, Vec<VariableId>), (BlockId, Vec<VariableId>)> =
+        HashMap::new();
+
+    for block_id in dominators.reverse_postorder.clone() {
+        let block = &mut lowered.blocks[block_id];
+        let mut statements = Vec::with_capacity(block.statements.len());
+        for mut statement in std::mem::take(&mut block.statements) {
+            rebind_statement_inputs(&mut statement, &rebinding);
+
+            if let Some(key) = cse_key(db, &statement) {
+                if let Some((def_block, prev_outputs)) = seen.get(&key) {
+                    if dominators.dominates(*def_block, block_id) {
+                        for (output, prev_output) in
+                            statement_outputs(&statement).iter().zip(prev_outputs)
+                        {
+                            rebinding.insert(*output, *prev_output);
+                        }
+                        // Drop the now-redundant statement.
+                        continue;
+                    }
+                }
+                seen.insert(key, (block_id, statement_outputs(&statement)));
+            }
+            statements.push(statement);
+        }
+        lowered.blocks[block_id].statements = statements;
+        rebind_block_end(&mut lowered.blocks[block_id].end, &rebinding);
+    }
+}
+
+/// Returns the CSE key for `statement`, or `None` if it is not safe to deduplicate.
+fn cse_key(db: &dyn LoweringGroup, statement: &Statement) -> Option<(FunctionId, Vec<VariableId>)> {
+    let Statement::Call(StatementCall { function, inputs, .. }) = statement else {
+        return None;
+    };
+    if !is_pure_and_deterministic(db, *function) {
+        return None;
+    }
+    Some((*function, inputs.iter().map(|input| input.var_id).collect_vec()))
+}
+
+/// Whether calls to `function` are free of side effects and always return the same outputs for
+/// the same inputs, and are therefore safe to collapse.
+///
+/// This is intentionally conservative: only an explicit allowlist of core-library extern
+/// functions known to be pure (felt252 arithmetic, bool logic, array reads and box/unbox) is
+/// considered safe. User-defined
+/// (`GenericFunctionId::Free`/`Impl`/`Trait`) functions are never collapsed here, since proving
+/// their purity would require a whole-program effect analysis this pass doesn't have; likewise
+/// any extern not on the allowlist (gas, dictionaries, StarkNet syscalls, and anything else not
+/// explicitly vetted) is excluded by default.
+///
+/// Branching libfuncs - anything lowered to a `MatchInfo::Extern` rather than a `Statement::Call`
+/// (the overflow-checked arithmetic ops, `felt252_is_zero`, ...) are deliberately left off: they
+/// never reach `cse_key`, which only inspects `Statement::Call`, so listing them here wouldn't
+/// collapse anything and would misleadingly imply they're handled.
+fn is_pure_and_deterministic(db: &dyn LoweringGroup, function: FunctionId) -> bool {
+    let concrete_function = db.lookup_intern_function(function).function;
+    let GenericFunctionId::Extern(extern_function_id) = concrete_function.generic_function else {
+        return false;
+    };
+    let semantic_db = db.upcast();
+    const PURE_EXTERN_FUNCTIONS: &[&str] = &[
+        "felt252_add",
+        "felt252_sub",
+        "felt252_mul",
+        "bool_not_impl",
+        "bool_and_impl",
+        "bool_or_impl",
+        "bool_xor_impl",
+        "array_len",
+        "array_at",
+        "box_forward_snapshot",
+        "unbox",
+    ];
+    PURE_EXTERN_FUNCTIONS.contains(&extern_function_id.name(semantic_db).as_str())
+}
+
+fn statement_outputs(statement: &Statement) -> Vec<VariableId> {
+    match statement {
+        Statement::Call(call) => call.outputs.clone(),
+        _ => vec![],
+    }
+}
+
+fn rebind_var_usage(usage: &mut VarUsage, rebinding: &OrderedHashMap<VariableId, VariableId>) {
+    if let Some(replacement) = rebinding.get(&usage.var_id) {
+        usage.var_id = *replacement;
+    }
+}
+
+/// Rewrites every input of `statement` - whichever variant it is - to its CSE-rebound variable,
+/// so a statement consuming a deduplicated value never references the deleted definition.
+fn rebind_statement_inputs(statement: &mut Statement, rebinding: &OrderedHashMap<VariableId, VariableId>) {
+    match statement {
+        Statement::Literal(_) => {}
+        Statement::Call(StatementCall { inputs, .. }) => {
+            for input in inputs.iter_mut() {
+                rebind_var_usage(input, rebinding);
+            }
+        }
+        Statement::StructConstruct(StatementStructConstruct { inputs, .. }) => {
+            for input in inputs.iter_mut() {
+                rebind_var_usage(input, rebinding);
+            }
+        }
+        Statement::StructDestructure(StatementStructDestructure { input, .. }) => {
+            rebind_var_usage(input, rebinding);
+        }
+        Statement::EnumConstruct(StatementEnumConstruct { input, .. }) => {
+            rebind_var_usage(input, rebinding);
+        }
+        Statement::Snapshot(statement_snapshot) => {
+            rebind_var_usage(&mut statement_snapshot.input, rebinding);
+        }
+        Statement::Desnap(statement_desnap) => {
+            rebind_var_usage(&mut statement_desnap.input, rebinding);
+        }
+    }
+}
+
+fn rebind_block_end(end: &mut FlatBlockEnd, rebinding: &OrderedHashMap<VariableId, VariableId>) {
+    match end {
+        FlatBlockEnd::Return(vars, _) => {
+            for var in vars.iter_mut() {
+                rebind_var_usage(var, rebinding);
+            }
+        }
+        FlatBlockEnd::Goto(_, remapping) => {
+            for (_, var) in remapping.iter_mut() {
+                rebind_var_usage(var, rebinding);
+            }
+        }
+        FlatBlockEnd::Match { info } => match info {
+            MatchInfo::Enum(match_enum) => rebind_var_usage(&mut match_enum.input, rebinding),
+            MatchInfo::Extern(match_extern) => {
+                for input in match_extern.inputs.iter_mut() {
+                    rebind_var_usage(input, rebinding);
+                }
+            }
+            // Conservatively skip rebinding for any match kind not accounted for above, rather
+            // than failing to compile against a `MatchInfo` variant this pass doesn't yet know:
+            // worst case such a variant keeps referencing a dead (but still present and valid)
+            // duplicate statement, which is a missed optimization, not a correctness bug.
+            #[allow(unreachable_patterns)]
+            _ => {}
+        },
+        FlatBlockEnd::Panic(var) => rebind_var_usage(var, rebinding),
+        FlatBlockEnd::NotSet => {}
+    }
+}
+
+/// A block's dominator tree, computed over the CFG induced by `FlatBlockEnd` successors.
+struct Dominators {
+    /// Blocks in reverse-postorder from the entry block; dominance queries and the CSE walk both
+    /// rely on processing blocks in this order.
+    reverse_postorder: Vec<BlockId>,
+    /// Maps each block to its immediate dominator. The entry block dominates itself.
+    immediate_dominator: HashMap<BlockId, BlockId>,
+}
+
+impl Dominators {
+    fn compute(lowered: &FlatLowered) -> Self {
+        let entry = BlockId(0);
+        let reverse_postorder = reverse_postorder(lowered, entry);
+        let order_index: HashMap<BlockId, usize> =
+            reverse_postorder.iter().enumerate().map(|(i, &b)| (b, i)).collect();
+
+        let mut predecessors: HashMap<BlockId, Vec<BlockId>> = HashMap::new();
+        for (i, block) in lowered.blocks.iter().enumerate() {
+            for successor in successors(&block.end) {
+                predecessors.entry(successor).or_default().push(BlockId(i));
+            }
+        }
+
+        // Standard Cooper-Harvey-Kennedy iterative dominator algorithm.
+        let mut immediate_dominator: HashMap<BlockId, BlockId> = HashMap::new();
+        immediate_dominator.insert(entry, entry);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &block in reverse_postorder.iter().skip(1) {
+                let mut new_idom = None;
+                for &pred in predecessors.get(&block).into_iter().flatten() {
+                    if !immediate_dominator.contains_key(&pred) {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => pred,
+                        Some(cur) => intersect(cur, pred, &immediate_dominator, &order_index),
+                    });
+                }
+                if let Some(new_idom) = new_idom {
+                    if immediate_dominator.get(&block) != Some(&new_idom) {
+                        immediate_dominator.insert(block, new_idom);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        Self { reverse_postorder, immediate_dominator }
+    }
+
+    /// Whether `maybe_dominator` dominates `block` (a block always dominates itself).
+    fn dominates(&self, maybe_dominator: BlockId, block: BlockId) -> bool {
+        let mut current = block;
+        loop {
+            if current == maybe_dominator {
+                return true;
+            }
+            let Some(&idom) = self.immediate_dominator.get(&current) else {
+                return false;
+            };
+            if idom == current {
+                // Reached the entry block without finding `maybe_dominator`.
+                return false;
+            }
+            current = idom;
+        }
+    }
+}
+
+/// Finds the closest common dominator of two blocks already known to be dominated, by walking up
+/// from the deeper one (in reverse-postorder index) until both sides match.
+fn intersect(
+    mut a: BlockId,
+    mut b: BlockId,
+    immediate_dominator: &HashMap<BlockId, BlockId>,
+    order_index: &HashMap<BlockId, usize>,
+) -> BlockId {
+    while a != b {
+        while order_index[&a] > order_index[&b] {
+            a = immediate_dominator[&a];
+        }
+        while order_index[&b] > order_index[&a] {
+            b = immediate_dominator[&b];
+        }
+    }
+    a
+}
+
+fn successors(end: &FlatBlockEnd) -> Vec<BlockId> {
+    match end {
+        FlatBlockEnd::Goto(target, _) => vec![*target],
+        FlatBlockEnd::Match { info } => match info {
+            MatchInfo::Enum(match_enum) => match_enum.arms.iter().map(|arm| arm.block_id).collect(),
+            MatchInfo::Extern(match_extern) => {
+                match_extern.arms.iter().map(|arm| arm.block_id).collect()
+            }
+            #[allow(unreachable_patterns)]
+            _ => vec![],
+        },
+        FlatBlockEnd::Return(..) | FlatBlockEnd::Panic(_) | FlatBlockEnd::NotSet => vec![],
+    }
+}
+
+/// Depth-first reverse-postorder over the CFG reachable from `entry`.
+fn reverse_postorder(lowered: &FlatLowered, entry: BlockId) -> Vec<BlockId> {
+    let mut visited = vec![false; lowered.blocks.len()];
+    let mut postorder = Vec::with_capacity(lowered.blocks.len());
+    let mut stack = vec![(entry, false)];
+    while let Some((block, expanded)) = stack.pop() {
+        if expanded {
+            postorder.push(block);
+            continue;
+        }
+        if visited[block.0] {
+            continue;
+        }
+        visited[block.0] = true;
+        stack.push((block, true));
+        for successor in successors(&lowered.blocks[block].end) {
+            if !visited[successor.0] {
+                stack.push((successor, false));
+            }
+        }
+    }
+    postorder.reverse();
+    postorder
+}
@@ -0,0 +1,16 @@
+use crate::db::LoweringGroup;
+use crate::FlatLowered;
+
+pub mod cse;
+
+/// Runs the post-unrolling cleanup optimizations over `lowered`, in place.
+///
+/// This is meant to be called from the lowering group's optimized-lowering query, right after
+/// loop unrolling, so that the duplicate pure calls unrolling introduces get collapsed before the
+/// function is handed off to Sierra generation - but that query, along with the rest of this
+/// crate's root (`lib.rs`/`db.rs`, where `mod optimizations;` would need to be declared and this
+/// function called), isn't part of this tree. Nothing in this tree declares this module or calls
+/// this function.
+pub fn apply_post_unrolling_optimizations(db: &dyn LoweringGroup, lowered: &mut FlatLowered) {
+    cse::cse(db, lowered);
+}